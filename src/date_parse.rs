@@ -0,0 +1,362 @@
+//! Lenient, human-friendly date/time parsing for the CLI's `date` argument.
+//!
+//! `NaiveDate::parse_from_str(_, "%Y-%m-%d")` rejects anything that isn't
+//! exactly ISO-ish, which is annoying to type by hand. This module tokenizes
+//! the input into runs of digits, letters, and separators, then classifies
+//! the tokens (month/weekday names, am/pm markers, a 4-digit year, an
+//! `HH:MM(:SS)` run, an optional `±HH:MM` offset) and assembles them into a
+//! date. Anything it can't place (missing year, missing day, ...) falls
+//! back to a sensible default rather than failing outright, so partial
+//! strings like `"Jan 2024"` still resolve.
+
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+/// The result of parsing a user-supplied date string.
+///
+/// A bare calendar date (no time-of-day found) resolves to `Date`; anything
+/// with a clock time attached resolves to `DateTime`, defaulting to the
+/// local offset when none was given explicitly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParsedDate {
+    Date(NaiveDate),
+    DateTime(DateTime<FixedOffset>),
+}
+
+impl ParsedDate {
+    /// The calendar date, dropping any time-of-day component.
+    pub fn naive_date(&self) -> NaiveDate {
+        match self {
+            ParsedDate::Date(d) => *d,
+            ParsedDate::DateTime(dt) => dt.naive_local().date(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Num(String),
+    Alpha(String),
+    Sep(char),
+}
+
+fn tokenize(s: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_digit() {
+            let mut run = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                run.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Num(run));
+        } else if c.is_alphabetic() {
+            let mut run = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_alphabetic()) {
+                run.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Alpha(run));
+        } else if c.is_whitespace() {
+            chars.next();
+        } else {
+            tokens.push(Token::Sep(c));
+            chars.next();
+        }
+    }
+
+    tokens
+}
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+fn match_month(word: &str) -> Option<u32> {
+    let w = word.to_lowercase();
+    if w.len() < 3 {
+        return None;
+    }
+    MONTHS.iter().position(|m| m.starts_with(&w)).map(|i| i as u32 + 1)
+}
+
+fn is_weekday(word: &str) -> bool {
+    let w = word.to_lowercase();
+    w.len() >= 3 && WEEKDAYS.iter().any(|d| d.starts_with(&w))
+}
+
+fn match_ampm(word: &str) -> Option<bool> {
+    match word.to_lowercase().as_str() {
+        "am" => Some(false),
+        "pm" => Some(true),
+        _ => None,
+    }
+}
+
+/// Parses a `HH:MM(:SS)` run starting at `tokens[start]`, plus a trailing
+/// `±HH:MM` offset or `Z` marker if one follows, and returns the index just
+/// past everything it consumed.
+fn parse_time_run(
+    tokens: &[Token],
+    start: usize,
+    time: &mut Option<(u32, u32, u32)>,
+    offset_minutes: &mut Option<i32>,
+) -> usize {
+    let mut i = start;
+
+    let hh: u32 = match &tokens[i] {
+        Token::Num(n) => n.parse().unwrap_or(0),
+        _ => 0,
+    };
+    i += 2; // the hour and its trailing ':'
+
+    let mm: u32 = match tokens.get(i) {
+        Some(Token::Num(n)) => n.parse().unwrap_or(0),
+        _ => 0,
+    };
+    i += 1;
+
+    let mut ss = 0;
+    if matches!(tokens.get(i), Some(Token::Sep(':'))) && matches!(tokens.get(i + 1), Some(Token::Num(_)))
+    {
+        if let Some(Token::Num(n)) = tokens.get(i + 1) {
+            ss = n.parse().unwrap_or(0);
+        }
+        i += 2;
+    }
+
+    *time = Some((hh, mm, ss));
+
+    match tokens.get(i) {
+        Some(Token::Sep(sign @ ('+' | '-'))) => {
+            let sign = *sign;
+            if let Some(Token::Num(oh_str)) = tokens.get(i + 1) {
+                let oh: i32 = oh_str.parse().unwrap_or(0);
+                i += 2;
+
+                let mut om = 0;
+                if matches!(tokens.get(i), Some(Token::Sep(':')))
+                    && matches!(tokens.get(i + 1), Some(Token::Num(_)))
+                {
+                    if let Some(Token::Num(n)) = tokens.get(i + 1) {
+                        om = n.parse().unwrap_or(0);
+                    }
+                    i += 2;
+                }
+
+                let total = oh * 60 + om;
+                *offset_minutes = Some(if sign == '-' { -total } else { total });
+            }
+        }
+        Some(Token::Alpha(w)) if w.eq_ignore_ascii_case("z") => {
+            *offset_minutes = Some(0);
+            i += 1;
+        }
+        _ => {}
+    }
+
+    i
+}
+
+/// Parses a human-entered date/time string.
+///
+/// Accepts things like `Jan 29 2024`, `2024/1/29`, `29.01.2024 14:30`, and
+/// `2024-01-29T09:15:00+02:00`. Tries `chrono`'s own RFC 3339 parser
+/// first since that covers the fully-qualified case exactly.
+pub fn parse_date(input: &str) -> Result<ParsedDate, String> {
+    let input = input.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Ok(ParsedDate::DateTime(dt));
+    }
+
+    let tokens = tokenize(input);
+
+    let mut month: Option<u32> = None;
+    let mut day: Option<u32> = None;
+    let mut ampm: Option<bool> = None;
+    let mut time: Option<(u32, u32, u32)> = None;
+    let mut offset_minutes: Option<i32> = None;
+    let mut date_nums: Vec<(i64, usize)> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Sep(_) => i += 1,
+            Token::Alpha(w) => {
+                if let Some(m) = match_month(w) {
+                    month = Some(m);
+                } else if let Some(pm) = match_ampm(w) {
+                    ampm = Some(pm);
+                } else if w.eq_ignore_ascii_case("z") {
+                    offset_minutes = Some(0);
+                } else if is_weekday(w) {
+                    // informational only, doesn't affect the resolved date
+                }
+                i += 1;
+            }
+            Token::Num(_) if matches!(tokens.get(i + 1), Some(Token::Sep(':'))) => {
+                i = parse_time_run(&tokens, i, &mut time, &mut offset_minutes);
+            }
+            Token::Num(n) => {
+                date_nums.push((n.parse().map_err(|_| format!("bad number in {input:?}"))?, n.len()));
+                i += 1;
+            }
+        }
+    }
+
+    if date_nums.is_empty() && month.is_none() {
+        return Err(format!("no date found in {input:?}"));
+    }
+
+    let year_pos = date_nums.iter().position(|(_, len)| *len == 4);
+    let year = year_pos
+        .map(|p| date_nums[p].0 as i32)
+        .unwrap_or_else(|| Local::now().date_naive().year_ce().1 as i32);
+
+    let mut remaining: Vec<i64> = date_nums
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| Some(*idx) != year_pos)
+        .map(|(_, (v, _))| *v)
+        .collect();
+
+    // A leading year (`2024/1/29`) reads month-then-day; a trailing year
+    // (`29.01.2024`) reads day-then-month, matching the two common locale
+    // orderings.
+    let year_is_leading = year_pos == Some(0);
+
+    if month.is_none() && !remaining.is_empty() {
+        if year_is_leading || remaining.len() == 1 {
+            month = Some(remaining.remove(0) as u32);
+        } else {
+            day = Some(remaining.remove(0) as u32);
+        }
+    }
+    if !remaining.is_empty() {
+        if month.is_none() {
+            month = Some(remaining.remove(0) as u32);
+        } else {
+            day = Some(remaining.remove(0) as u32);
+        }
+    }
+
+    let month = month.unwrap_or(1);
+    let day = day.unwrap_or(1);
+
+    // A value over 12 can't be a month and a value over 31 can't be a day;
+    // if our positional guess produced that, the two were swapped.
+    let (month, day) = if month > 12 && day <= 12 { (day, month) } else { (month, day) };
+
+    if month == 0 || month > 12 {
+        return Err(format!("no valid month in {input:?}"));
+    }
+    if day == 0 || day > 31 {
+        return Err(format!("no valid day in {input:?}"));
+    }
+
+    let date = NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| format!("invalid date in {input:?}"))?;
+
+    let Some((mut hh, mm, ss)) = time else {
+        return Ok(ParsedDate::Date(date));
+    };
+
+    match ampm {
+        Some(true) if hh < 12 => hh += 12,
+        Some(false) if hh == 12 => hh = 0,
+        _ => {}
+    }
+
+    let naive_time = NaiveTime::from_hms_opt(hh, mm, ss).ok_or_else(|| format!("invalid time in {input:?}"))?;
+    let naive_dt = NaiveDateTime::new(date, naive_time);
+
+    let dt = match offset_minutes {
+        Some(total) => {
+            let offset = FixedOffset::east_opt(total * 60).ok_or_else(|| format!("invalid utc offset in {input:?}"))?;
+            offset
+                .from_local_datetime(&naive_dt)
+                .single()
+                .ok_or_else(|| format!("ambiguous local time in {input:?}"))?
+        }
+        None => Local
+            .from_local_datetime(&naive_dt)
+            .single()
+            .ok_or_else(|| format!("ambiguous local time in {input:?}"))?
+            .fixed_offset(),
+    };
+
+    Ok(ParsedDate::DateTime(dt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    #[test]
+    fn swaps_month_and_day_when_month_is_out_of_range() {
+        // Leading year reads month-then-day, but 13 can't be a month, so it
+        // and the day swap to make "2024/13/5" mean May 13.
+        let parsed = parse_date("2024/13/5").unwrap();
+        assert_eq!(parsed.naive_date(), NaiveDate::from_ymd_opt(2024, 5, 13).unwrap());
+    }
+
+    #[test]
+    fn rejects_dates_where_neither_ordering_is_valid() {
+        assert!(parse_date("2024/13/32").is_err());
+    }
+
+    #[test]
+    fn rejects_input_with_no_date_signal() {
+        assert!(parse_date("hello world").is_err());
+        assert!(parse_date("foo").is_err());
+        assert!(parse_date("").is_err());
+        assert!(parse_date("   ").is_err());
+        assert!(parse_date("pm").is_err());
+    }
+
+    #[test]
+    fn parses_explicit_utc_offset() {
+        let parsed = parse_date("29.01.2024 09:15+02:00").unwrap();
+        let ParsedDate::DateTime(dt) = parsed else {
+            panic!("expected a DateTime, got {parsed:?}");
+        };
+
+        assert_eq!(dt.offset().local_minus_utc(), 2 * 60 * 60);
+        assert_eq!(dt.naive_utc().hour(), 7);
+        assert_eq!(dt.naive_utc().minute(), 15);
+    }
+
+    #[test]
+    fn parses_trailing_z_as_utc() {
+        let parsed = parse_date("2024-01-29T09:15Z").unwrap();
+        let ParsedDate::DateTime(dt) = parsed else {
+            panic!("expected a DateTime, got {parsed:?}");
+        };
+
+        assert_eq!(dt.offset().local_minus_utc(), 0);
+        assert_eq!(dt.naive_local(), NaiveDate::from_ymd_opt(2024, 1, 29).unwrap().and_hms_opt(9, 15, 0).unwrap());
+    }
+}
@@ -0,0 +1,884 @@
+//! The milestone-finding engine: digit-pattern finders, the zoned delta
+//! arithmetic, and the occurrence stream that ranks them. Kept separate from
+//! `main` so the engine can be driven by something other than the CLI (a
+//! test, another crate, a `--format json` consumer) via [`next_events`].
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fmt::Display;
+
+use chrono::{naive::NaiveDate, DateTime, Datelike, Duration, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+fn digit_count(n: u64, base: u8) -> u32 {
+    (n as f64 + 1.).log(base as f64).ceil() as u32
+}
+
+fn first_digit(n: u64, base: u8) -> u8 {
+    (n / (base as u64).pow(digit_count(n, base) - 1)) as u8
+}
+
+fn add_months_to_date(date: &NaiveDate, mut months: u64) -> NaiveDate {
+    let mut year = date.year();
+    let mut month = date.month();
+
+    months += month as u64 - 1;
+
+    year += months as i32 / 12;
+    month = (months as u32 % 12) + 1;
+
+    NaiveDate::from_ymd(year, month, date.day())
+}
+
+/// Calendar-arithmetic month addition for a zoned instant: adds months to
+/// the wall-clock date/time and re-anchors it in `date`'s zone, so DST
+/// transitions are respected the same way a human counting months would.
+fn add_months_to_datetime<Tz: TimeZone + Clone>(date: &DateTime<Tz>, months: u64) -> DateTime<Tz> {
+    let naive = date.naive_local();
+    let new_date = add_months_to_date(&naive.date(), months);
+    let new_naive = NaiveDateTime::new(new_date, naive.time());
+
+    match date.timezone().from_local_datetime(&new_naive) {
+        chrono::LocalResult::Single(dt) => dt,
+        chrono::LocalResult::Ambiguous(dt, _) => dt,
+        chrono::LocalResult::None => date.timezone().from_utc_datetime(&new_naive),
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Pattern {
+    value: u64,
+    base: u8,
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.base {
+            10 => write!(f, "{}", self.value),
+            16 => write!(f, "{:#0x}", self.value),
+            _ => panic!("unhandled base {}", self.base),
+        }
+    }
+}
+
+trait PatternFinder {
+    /// The name used to select this finder via `--patterns`.
+    fn name(&self) -> &'static str;
+
+    fn find_next(&self, n: u64, base: u8) -> Pattern;
+
+    /// Like `find_next`, but strictly after a value already emitted, so an
+    /// occurrence stream can advance past a pattern it just yielded.
+    fn find_next_after(&self, n: u64, base: u8) -> Pattern {
+        self.find_next(n + 1, base)
+    }
+}
+
+#[derive(Default)]
+struct RoundNumberFinder {}
+
+impl PatternFinder for RoundNumberFinder {
+    fn name(&self) -> &'static str {
+        "round"
+    }
+
+    fn find_next(&self, n: u64, base: u8) -> Pattern {
+        const MAX_EXPONENT: u32 = 19;
+
+        // n - 1, so that powers of base are handled OK
+        let digits = digit_count(n - 1, base);
+        let first_digit = first_digit(n - 1, base);
+
+        if digits > MAX_EXPONENT || digits < 2 {
+            Pattern { value: 0, base }
+        } else {
+            Pattern {
+                value: ((first_digit + 1) as u64) * (base as u64).pow(digits - 1),
+                base,
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct RepeatedNumberFinder {}
+
+impl RepeatedNumberFinder {
+    fn get_repeat_number(&self, first_digit: u8, digits: u32, base: u8) -> u64 {
+        let mut res = first_digit as u64;
+
+        for _ in 1..digits {
+            res = res * (base as u64) + first_digit as u64;
+        }
+
+        res
+    }
+}
+
+impl PatternFinder for RepeatedNumberFinder {
+    fn name(&self) -> &'static str {
+        "repeated"
+    }
+
+    fn find_next(&self, n: u64, base: u8) -> Pattern {
+        let digits = digit_count(n, base);
+        let first_digit = first_digit(n, base);
+
+        let res = self.get_repeat_number(first_digit, digits, base);
+
+        if res >= n {
+            Pattern { value: res, base }
+        } else {
+            Pattern {
+                value: self.get_repeat_number(first_digit + 1, digits, base),
+                base,
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct SequenceFinder {
+    reverse: bool,
+}
+
+impl PatternFinder for SequenceFinder {
+    fn name(&self) -> &'static str {
+        if self.reverse {
+            "sequence-reverse"
+        } else {
+            "sequence"
+        }
+    }
+
+    fn find_next(&self, n: u64, base: u8) -> Pattern {
+        let mut res = 1_u64;
+
+        for i in 2..=(base as u64 - 1) {
+            if res >= n {
+                return Pattern { value: res, base };
+            }
+            if self.reverse {
+                let d = digit_count(res, base);
+                res += (base as u64).pow(d) * i;
+            } else {
+                res = res * (base as u64) + i;
+            }
+        }
+
+        Pattern { value: 0, base }
+    }
+}
+
+fn digits_of(mut n: u64, base: u8) -> Vec<u8> {
+    if n == 0 {
+        return vec![0];
+    }
+
+    let mut digits = Vec::new();
+    while n > 0 {
+        digits.push((n % base as u64) as u8);
+        n /= base as u64;
+    }
+    digits.reverse();
+    digits
+}
+
+fn value_of(digits: &[u8], base: u8) -> u64 {
+    digits.iter().fold(0u64, |acc, &d| acc * base as u64 + d as u64)
+}
+
+#[derive(Default)]
+struct PalindromeFinder {}
+
+impl PatternFinder for PalindromeFinder {
+    fn name(&self) -> &'static str {
+        "palindrome"
+    }
+
+    fn find_next(&self, n: u64, base: u8) -> Pattern {
+        const MAX_DIGITS: usize = 19;
+
+        let digits = digits_of(n, base);
+        let len = digits.len();
+        let half_len = (len + 1) / 2;
+
+        // Mirror the left half onto the right; if that's already >= n we're
+        // done, since it's the largest palindrome sharing that left half.
+        let mut mirrored = digits.clone();
+        for i in 0..len / 2 {
+            mirrored[len - 1 - i] = mirrored[i];
+        }
+        let candidate = value_of(&mirrored, base);
+        if candidate >= n {
+            return Pattern { value: candidate, base };
+        }
+
+        // Otherwise the mirrored candidate undershot n: bump the left half
+        // by one (carrying through it) and mirror again.
+        let mut left = digits[..half_len].to_vec();
+        let mut i = half_len;
+        loop {
+            if i == 0 {
+                return match (base as u64).checked_pow(len as u32) {
+                    Some(p) if len < MAX_DIGITS => Pattern { value: p + 1, base },
+                    _ => Pattern { value: 0, base },
+                };
+            }
+            i -= 1;
+            if left[i] + 1 < base {
+                left[i] += 1;
+                break;
+            }
+            left[i] = 0;
+        }
+
+        let mirror_src = if len % 2 == 0 { &left[..] } else { &left[..left.len() - 1] };
+        let mut full = left.clone();
+        full.extend(mirror_src.iter().rev());
+
+        Pattern { value: value_of(&full, base), base }
+    }
+}
+
+#[derive(Default)]
+struct RepunitFinder {}
+
+impl PatternFinder for RepunitFinder {
+    fn name(&self) -> &'static str {
+        "repunit"
+    }
+
+    fn find_next(&self, n: u64, base: u8) -> Pattern {
+        const MAX_DIGITS: u32 = 19;
+
+        let mut value = 0_u64;
+        let mut digits = 0;
+
+        loop {
+            digits += 1;
+            value = match value.checked_mul(base as u64).and_then(|v| v.checked_add(1)) {
+                Some(v) => v,
+                None => return Pattern { value: 0, base },
+            };
+
+            if value >= n {
+                return Pattern { value, base };
+            }
+            if digits >= MAX_DIGITS {
+                return Pattern { value: 0, base };
+            }
+        }
+    }
+}
+
+struct MultiPatternFinder {
+    pattern_finders: Vec<Box<dyn PatternFinder>>,
+}
+
+impl MultiPatternFinder {
+    fn new() -> Self {
+        let pattern_finders: Vec<Box<dyn PatternFinder>> = vec![
+            Box::new(RoundNumberFinder::default()),
+            Box::new(RepeatedNumberFinder::default()),
+            Box::new(SequenceFinder::default()),
+            Box::new(SequenceFinder { reverse: true }),
+            Box::new(PalindromeFinder::default()),
+            Box::new(RepunitFinder::default()),
+        ];
+
+        Self { pattern_finders }
+    }
+
+    /// Builds a finder restricted to the given `--patterns` names (see
+    /// [`PatternFinder::name`]), falling back to every finder when none are
+    /// given so the flag is optional.
+    fn with_names(names: &[String]) -> Self {
+        if names.is_empty() {
+            return Self::new();
+        }
+
+        let pattern_finders: Vec<Box<dyn PatternFinder>> = Self::new()
+            .pattern_finders
+            .into_iter()
+            .filter(|f| names.iter().any(|n| n == f.name()))
+            .collect();
+
+        Self { pattern_finders }
+    }
+
+    fn find_patterns(&self, n: u64, base: u8) -> Vec<Pattern> {
+        let mut res: Vec<Pattern> = self
+            .pattern_finders
+            .iter()
+            .map(|f| f.find_next(n, base))
+            .filter(|p| p.value != 0)
+            .collect();
+
+        res.sort_by(|l, r| l.value.cmp(&r.value));
+        res
+    }
+
+}
+
+impl PatternFinder for MultiPatternFinder {
+    fn name(&self) -> &'static str {
+        "multi"
+    }
+
+    fn find_next(&self, n: u64, base: u8) -> Pattern {
+        let mut best_pattern = Pattern::default();
+        let mut best_delta = u64::MAX;
+
+        for p in self.find_patterns(n, base) {
+            let delta = p.value - n;
+
+            if delta < best_delta {
+                best_delta = delta;
+                best_pattern = p;
+            }
+        }
+
+        best_pattern
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum TimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+impl TimeUnit {
+    fn to_seconds(&self) -> Option<f64> {
+        match self {
+            TimeUnit::Second => Some(1.),
+            TimeUnit::Minute => Some(60.),
+            TimeUnit::Hour => Some(60. * 60.),
+            TimeUnit::Day => Some(60. * 60. * 24.),
+            TimeUnit::Week => Some(60. * 60. * 24. * 7.),
+            TimeUnit::Month => None,
+        }
+    }
+}
+
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeUnit::Second => write!(f, "second"),
+            TimeUnit::Minute => write!(f, "minute"),
+            TimeUnit::Hour => write!(f, "hour"),
+            TimeUnit::Day => write!(f, "day"),
+            TimeUnit::Week => write!(f, "week"),
+            TimeUnit::Month => write!(f, "month"),
+        }
+    }
+}
+
+// TODO: fix below
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct DeltaCandidate {
+    pattern: Pattern,
+    unit: TimeUnit,
+}
+
+impl DeltaCandidate {
+    fn to_seconds(&self) -> Option<u64> {
+        match self.unit.to_seconds() {
+            Some(s) => Some(self.pattern.value * (s as u64)),
+            None => None,
+        }
+    }
+
+    /// Advances a zoned instant by this candidate's delta: a real `Duration`
+    /// for second/minute/hour/day/week units (DST-aware since it operates on
+    /// the instant), calendar arithmetic for months.
+    fn add_to_date<Tz: TimeZone + Clone>(&self, date: &DateTime<Tz>) -> DateTime<Tz> {
+        if let Some(s) = self.to_seconds() {
+            return date.clone() + Duration::seconds(s as i64);
+        }
+
+        match self.unit {
+            TimeUnit::Month => add_months_to_datetime(date, self.pattern.value),
+            _ => date.clone(),
+        }
+    }
+}
+
+impl Display for DeltaCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}{}",
+            self.pattern,
+            self.unit,
+            if self.pattern.value > 1 { "s" } else { "" }
+        )
+    }
+}
+
+/// A [`DeltaCandidate`] tagged with the reference point it was measured
+/// from: empty for the usual "delta from now", or an epoch's name (e.g.
+/// "the Unix epoch") for an absolute milestone.
+#[derive(Clone)]
+struct RankedCandidate {
+    candidate: DeltaCandidate,
+    origin: String,
+}
+
+/// One or more milestones that land on the same instant, e.g. both
+/// "1000 days" and "0x3e8 days" resolve to the same moment and are merged
+/// into a single occurrence.
+struct Occurrence<Tz: TimeZone> {
+    target: DateTime<Tz>,
+    candidates: Vec<RankedCandidate>,
+}
+
+/// A starting point to search for patterns from: `n` is the count (in
+/// `unit`s) to hand to the pattern finders, anchored at `anchor` so a match
+/// can be converted back into an instant.
+struct Seed<Tz: TimeZone> {
+    anchor: DateTime<Tz>,
+    origin: String,
+    unit: TimeUnit,
+    n: u64,
+}
+
+struct StreamEntry<Tz: TimeZone> {
+    target: DateTime<Tz>,
+    anchor: DateTime<Tz>,
+    origin: String,
+    finder_idx: usize,
+    candidate: DeltaCandidate,
+}
+
+impl<Tz: TimeZone> PartialEq for StreamEntry<Tz> {
+    fn eq(&self, other: &Self) -> bool {
+        self.target == other.target
+    }
+}
+
+impl<Tz: TimeZone> Eq for StreamEntry<Tz> {}
+
+impl<Tz: TimeZone> PartialOrd for StreamEntry<Tz> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Tz: TimeZone> Ord for StreamEntry<Tz> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max-heap; reverse so the nearest instant pops first.
+        other.target.cmp(&self.target)
+    }
+}
+
+fn push_pattern<Tz: TimeZone + Clone>(
+    heap: &mut BinaryHeap<StreamEntry<Tz>>,
+    finder_idx: usize,
+    pattern: Pattern,
+    unit: TimeUnit,
+    anchor: &DateTime<Tz>,
+    origin: &str,
+) {
+    if pattern.value == 0 {
+        return;
+    }
+
+    let candidate = DeltaCandidate { pattern, unit };
+    let target = candidate.add_to_date(anchor);
+    heap.push(StreamEntry {
+        target,
+        anchor: anchor.clone(),
+        origin: origin.to_string(),
+        finder_idx,
+        candidate,
+    });
+}
+
+/// Seeds a heap with one entry per `(finder, seed, base)` combination and
+/// returns the resulting occurrence stream, nearest first.
+fn build_stream<'a, Tz: TimeZone + Clone>(finder: &'a MultiPatternFinder, seeds: &[Seed<Tz>]) -> MilestoneStream<'a, Tz> {
+    let bases = [10_u8, 0x10_u8];
+    let mut heap = BinaryHeap::new();
+
+    for (finder_idx, pattern_finder) in finder.pattern_finders.iter().enumerate() {
+        for seed in seeds {
+            for base in bases {
+                let pattern = pattern_finder.find_next(seed.n, base);
+                push_pattern(&mut heap, finder_idx, pattern, seed.unit, &seed.anchor, &seed.origin);
+            }
+        }
+    }
+
+    MilestoneStream { finder, heap }
+}
+
+/// Yields upcoming milestones in chronological order across every
+/// `(finder, unit, base)` combination, nearest first.
+struct MilestoneStream<'a, Tz: TimeZone> {
+    finder: &'a MultiPatternFinder,
+    heap: BinaryHeap<StreamEntry<Tz>>,
+}
+
+impl<'a, Tz: TimeZone + Clone> Iterator for MilestoneStream<'a, Tz> {
+    type Item = Occurrence<Tz>;
+
+    fn next(&mut self) -> Option<Occurrence<Tz>> {
+        let first = self.heap.pop()?;
+        let mut popped = vec![(first.finder_idx, first.anchor, first.origin, first.candidate)];
+
+        while let Some(top) = self.heap.peek() {
+            if top.target != first.target {
+                break;
+            }
+            let entry = self.heap.pop().unwrap();
+            popped.push((entry.finder_idx, entry.anchor, entry.origin, entry.candidate));
+        }
+
+        for (finder_idx, anchor, origin, candidate) in &popped {
+            let finder = self.finder.pattern_finders[*finder_idx].as_ref();
+            let next = finder.find_next_after(candidate.pattern.value, candidate.pattern.base);
+            push_pattern(&mut self.heap, *finder_idx, next, candidate.unit, anchor, origin);
+        }
+
+        // Separately-registered finders (e.g. two copies of the same
+        // finder) can independently resolve to the same pattern/unit/origin;
+        // collapse those so the occurrence doesn't report one milestone
+        // twice.
+        let mut candidates: Vec<RankedCandidate> = Vec::new();
+        for (_, _, origin, candidate) in popped {
+            let dup = candidates
+                .iter()
+                .any(|c| c.candidate.pattern == candidate.pattern && c.candidate.unit == candidate.unit && c.origin == origin);
+            if !dup {
+                candidates.push(RankedCandidate { candidate, origin });
+            }
+        }
+
+        Some(Occurrence {
+            target: first.target,
+            candidates,
+        })
+    }
+}
+
+fn get_duration_str(d: &Duration) -> String {
+    if d.num_weeks() > 20 {
+        let months = d.num_days() * 2 / 61;
+        return format!("{months} months");
+    } else if d.num_days() > 99 {
+        return format!("{} weeks", d.num_weeks());
+    } else if d.num_hours() > 72 {
+        return format!("{} days", d.num_days());
+    } else if d.num_minutes() > 60 {
+        return format!("{} hours", d.num_hours());
+    } else if d.num_seconds() > 60 {
+        return format!("{} minutes", d.num_minutes());
+    } else {
+        return format!("{} seconds", d.num_seconds());
+    }
+}
+
+/// Resolves a wall-clock date/time in `tz`, picking the earlier instant on
+/// an ambiguous fall-back and falling back to a UTC-anchored read on a
+/// spring-forward gap, rather than panicking either way.
+pub fn anchor_in<Tz: TimeZone>(naive: NaiveDateTime, tz: &Tz) -> DateTime<Tz> {
+    match tz.from_local_datetime(&naive) {
+        chrono::LocalResult::Single(dt) => dt,
+        chrono::LocalResult::Ambiguous(dt, _) => dt,
+        chrono::LocalResult::None => tz.from_utc_datetime(&naive),
+    }
+}
+
+/// A single ranked milestone, flattened and serializable so it can be
+/// printed as text or emitted as JSON for another tool to consume.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    pub description: String,
+    pub target_date: String,
+    pub wait: String,
+    pub unit: TimeUnit,
+    pub base: u8,
+    pub value: u64,
+}
+
+fn relative_seeds<Tz: TimeZone + Clone>(anchor: DateTime<Tz>, delta: Duration, months: u64) -> Vec<Seed<Tz>> {
+    let seconds = delta.num_seconds().unsigned_abs();
+    let mut seeds = Vec::new();
+
+    for time_unit in TimeUnit::iter() {
+        if let Some(s) = time_unit.to_seconds() {
+            let n = (seconds as f64 / s).ceil() as u64;
+            seeds.push(Seed {
+                anchor: anchor.clone(),
+                origin: String::new(),
+                unit: time_unit,
+                n,
+            });
+        }
+    }
+
+    seeds.push(Seed {
+        anchor,
+        origin: String::new(),
+        unit: TimeUnit::Month,
+        n: months,
+    });
+
+    seeds
+}
+
+/// Seeds an absolute-epoch search: finds round/repeated/sequence *counts of
+/// seconds elapsed since `epoch`* (rather than a delta relative to `now`),
+/// e.g. a "two-billionth second since the Unix epoch" milestone.
+fn epoch_seed<Tz: TimeZone + Clone>(epoch: DateTime<Tz>, now: &DateTime<Tz>, name: String) -> Seed<Tz> {
+    let seconds = (now.clone() - epoch.clone()).num_seconds().unsigned_abs();
+    Seed {
+        anchor: epoch,
+        origin: name,
+        unit: TimeUnit::Second,
+        n: seconds,
+    }
+}
+
+/// One reference epoch to additionally rank absolute milestones against,
+/// alongside the usual relative-to-`now` ones. `name` labels the epoch in
+/// descriptions (e.g. "the Unix epoch"); `at` is the instant it starts from.
+pub struct Epoch<Tz: TimeZone> {
+    pub name: String,
+    pub at: DateTime<Tz>,
+}
+
+/// Knobs for [`next_events`].
+pub struct Config<Tz: TimeZone> {
+    pub count: usize,
+    pub epochs: Vec<Epoch<Tz>>,
+    /// Names of the pattern finders to search with, e.g. `"palindrome"` or
+    /// `"repunit"`; empty means "all of them".
+    pub patterns: Vec<String>,
+}
+
+/// Computes the milestones, counted from `anchor`, that fall on the next
+/// `config.count` distinct instants at or after `now`, nearest first,
+/// optionally ranking absolute epoch milestones (see [`Config::epochs`])
+/// alongside the relative-to-`now` ones in the same sorted output.
+/// Milestones that land on the same instant (e.g. a round decimal count and
+/// a round hex count hitting the same day) are adjacent in the result and
+/// share a `target_date`/`wait`, so the result can hold more than
+/// `config.count` entries.
+pub fn next_events<Tz: TimeZone + Clone>(anchor: DateTime<Tz>, now: DateTime<Tz>, config: &Config<Tz>) -> Vec<Milestone>
+where
+    Tz::Offset: Display,
+{
+    let naive_date = anchor.naive_local().date();
+    let cur_date = now.naive_local().date();
+
+    let delta = now.clone() - anchor.clone();
+
+    let mut months = (cur_date.year() as u64 - naive_date.year() as u64) * 12 + cur_date.month() as u64
+        - naive_date.month() as u64;
+
+    if cur_date.day() > naive_date.day() {
+        months += 1;
+    }
+
+    // The calendar math above is date-granular and can undershoot at clock
+    // precision: on the day of an exact N-month boundary it seeds N even
+    // when `anchor`'s time-of-day has already passed today, which would let
+    // the finder hand back a "next" milestone that's actually already in the
+    // past. Bump past it so every seeded month count resolves to an instant
+    // at or after `now`.
+    if add_months_to_datetime(&anchor, months) < now {
+        months += 1;
+    }
+
+    let mut seeds = relative_seeds(anchor, delta, months);
+    for epoch in &config.epochs {
+        seeds.push(epoch_seed(epoch.at.clone(), &now, epoch.name.clone()));
+    }
+
+    let finder = MultiPatternFinder::with_names(&config.patterns);
+    let mut milestones = Vec::new();
+
+    for occurrence in build_stream(&finder, &seeds).take(config.count) {
+        let wait = get_duration_str(&(occurrence.target.clone() - now.clone()));
+
+        // Sub-day units carry real clock precision, so show the full
+        // timestamp and zone rather than rounding to a bare date.
+        let is_sub_day = occurrence
+            .candidates
+            .iter()
+            .any(|c| matches!(c.candidate.unit, TimeUnit::Second | TimeUnit::Minute | TimeUnit::Hour));
+
+        let target_date = if is_sub_day {
+            occurrence.target.format("%Y-%m-%d %H:%M:%S %Z").to_string()
+        } else {
+            occurrence.target.format("%Y-%m-%d").to_string()
+        };
+
+        for ranked in &occurrence.candidates {
+            let description = if ranked.origin.is_empty() {
+                ranked.candidate.to_string()
+            } else {
+                format!("the {} since {}", ranked.candidate, ranked.origin)
+            };
+
+            milestones.push(Milestone {
+                description,
+                target_date: target_date.clone(),
+                wait: wait.clone(),
+                unit: ranked.candidate.unit,
+                base: ranked.candidate.pattern.base,
+                value: ranked.candidate.pattern.value,
+            });
+        }
+    }
+
+    milestones
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palindrome_carries_into_a_new_digit_count() {
+        let finder = PalindromeFinder::default();
+
+        // 999 is itself a palindrome; the next one after it rolls over to
+        // 1001, growing from 3 digits to 4.
+        assert_eq!(finder.find_next_after(999, 10).value, 1001);
+        assert_eq!(finder.find_next(1000, 10).value, 1001);
+    }
+
+    #[test]
+    fn palindrome_carries_within_its_left_half() {
+        // 12's left half ('1') must bump to '2' before mirroring, since
+        // mirroring '1' directly ('11') would undershoot 12.
+        let finder = PalindromeFinder::default();
+        assert_eq!(finder.find_next(12, 10).value, 22);
+    }
+
+    #[test]
+    fn palindrome_returns_n_itself_when_already_one() {
+        let finder = PalindromeFinder::default();
+        assert_eq!(finder.find_next(999, 10).value, 999);
+    }
+
+    #[test]
+    fn repunit_finds_next_all_ones_value() {
+        let finder = RepunitFinder::default();
+        assert_eq!(finder.find_next(5, 10).value, 11);
+        assert_eq!(finder.find_next(12, 10).value, 111);
+    }
+
+    #[test]
+    fn repunit_returns_sentinel_past_its_digit_cap() {
+        let finder = RepunitFinder::default();
+        assert_eq!(finder.find_next(u64::MAX, 10).value, 0);
+    }
+
+    #[test]
+    fn stream_merges_candidates_landing_on_the_same_instant() {
+        let anchor = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let seeds = vec![
+            Seed {
+                anchor,
+                origin: String::new(),
+                unit: TimeUnit::Day,
+                n: 7,
+            },
+            Seed {
+                anchor,
+                origin: String::new(),
+                unit: TimeUnit::Week,
+                n: 1,
+            },
+        ];
+        let finder = MultiPatternFinder::with_names(&["repeated".to_string()]);
+
+        // "7 days" and "1 week" (each found in both base 10 and base 16)
+        // all land on the same instant and should come back as one
+        // occurrence, not four.
+        let occurrence = build_stream(&finder, &seeds).next().expect("at least one occurrence");
+
+        assert_eq!(occurrence.target, anchor + Duration::days(7));
+        assert_eq!(occurrence.candidates.len(), 4);
+    }
+
+    #[test]
+    fn epoch_seed_counts_elapsed_seconds_since_the_epoch() {
+        let epoch = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let now = epoch + Duration::seconds(10);
+
+        let seed = epoch_seed(epoch, &now, "the test epoch".to_string());
+
+        assert_eq!(seed.anchor, epoch);
+        assert_eq!(seed.origin, "the test epoch");
+        assert_eq!(seed.unit, TimeUnit::Second);
+        assert_eq!(seed.n, 10);
+    }
+
+    #[test]
+    fn absolute_epoch_milestones_rank_alongside_relative_ones() {
+        let now = chrono::Utc.with_ymd_and_hms(2024, 6, 15, 0, 0, 0).unwrap();
+        // Far enough in the past that every relative (to `now`) milestone is
+        // hours away (the nearest, 4000 hours, is 16 hours out), so it can't
+        // beat the epoch milestone below.
+        let anchor = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        // 99 seconds before `now`, so "100 seconds since the epoch" lands
+        // 1 second from now: nearer than anything relative to `anchor`.
+        let epoch_at = now - Duration::seconds(99);
+
+        let config = Config {
+            count: 1,
+            epochs: vec![Epoch {
+                name: "the test epoch".to_string(),
+                at: epoch_at,
+            }],
+            patterns: vec!["round".to_string()],
+        };
+
+        let milestones = next_events(anchor, now, &config);
+
+        assert_eq!(milestones.len(), 1);
+        assert_eq!(milestones[0].description, "the 100 seconds since the test epoch");
+        assert_eq!(milestones[0].wait, "1 seconds");
+    }
+
+    #[test]
+    fn next_events_milestones_round_trip_through_json() {
+        let now = chrono::Utc.with_ymd_and_hms(2024, 6, 15, 0, 0, 0).unwrap();
+        // 45 elapsed seconds: the next base 16 repeated-digit count is
+        // "0x33" (51, 6 seconds from now), nearer than the next base 10 one
+        // (55) or "1 minute", so only one candidate lands on this instant.
+        let anchor = now - Duration::seconds(45);
+
+        let config = Config {
+            count: 1,
+            epochs: vec![],
+            patterns: vec!["repeated".to_string()],
+        };
+
+        let milestones = next_events(anchor, now, &config);
+        assert_eq!(milestones.len(), 1);
+        assert_eq!(milestones[0].description, "0x33 seconds");
+        assert_eq!(milestones[0].unit, TimeUnit::Second);
+        assert_eq!(milestones[0].base, 16);
+        assert_eq!(milestones[0].value, 51);
+
+        let json = serde_json::to_string(&milestones).expect("milestones should serialize");
+        let round_tripped: Vec<Milestone> = serde_json::from_str(&json).expect("milestones should deserialize");
+
+        assert_eq!(round_tripped.len(), milestones.len());
+        assert_eq!(round_tripped[0].value, milestones[0].value);
+        assert_eq!(round_tripped[0].description, milestones[0].description);
+    }
+}